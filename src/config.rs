@@ -179,6 +179,7 @@ pub fn parse_config() -> eyre::Result<()> {
 
 use clap::Parser;
 
+use crate::execution::engine::FuzzEngine;
 use crate::Deopt;
 /// Simple program to greet a person
 #[derive(Parser, Debug)]
@@ -218,6 +219,49 @@ pub struct Config {
     /// Run condensed fuzzers after the fuzz loop
     #[arg(long, default_value = "false")]
     pub fuzzer_run: bool,
+    /// Instruct the LLM to validate the input's minimal size/precondition and `return -1`
+    /// (libFuzzer's reject code) instead of falling through to `return 0`, so malformed
+    /// inputs that never reach meaningful library state are not added to the corpus.
+    #[arg(long = "reject-invalid", default_value = "false")]
+    pub reject_invalid_input: bool,
+    /// The fuzzing-engine backend used to build and drive generated harnesses. All engines
+    /// consume the same `LLVMFuzzerTestOneInput` ABI, so driver generation is unaffected.
+    #[arg(long, value_enum, default_value = "lib-fuzzer")]
+    pub engine: FuzzEngine,
+    /// Additional engines to drive after `--engine` on the same harness (e.g. `aflpp` and/or
+    /// `honggfuzz`), one after another rather than at the same time, each on its own per-engine
+    /// corpus that is copied into the shared corpus once that engine's run ends.
+    #[arg(long, value_enum, value_delimiter = ',')]
+    pub extra_engines: Vec<FuzzEngine>,
+    /// Whether to skip auto-generating a libFuzzer `-dict=` file from the collected
+    /// headers/APIs/custom-type context. Dictionary generation is enabled by default.
+    #[arg(long, default_value = "false")]
+    pub disable_dict_gen: bool,
+    /// Non-generating mode: instead of running the LLM loop, replay every file under this
+    /// directory (recursively) through the already-built binaries and report crash triage.
+    #[arg(long)]
+    pub replay: Option<std::path::PathBuf>,
+    /// Per-stage timeout (seconds) for the syntax-check stage before it is killed and
+    /// classified as a hang.
+    #[arg(long, default_value = "30")]
+    pub syntax_timeout: u64,
+    /// Per-stage timeout (seconds) for the link stage before it is killed and classified as a
+    /// hang.
+    #[arg(long, default_value = "60")]
+    pub link_timeout: u64,
+    /// Per-stage timeout (seconds) for the execute stage. The execute stage does not read it and
+    /// runs without a deadline.
+    #[arg(long, default_value_t = EXECUTION_TIMEOUT)]
+    pub execute_timeout: u64,
+    /// Timeout (seconds) for an external engine's (AFL++, honggfuzz) fuzz run; on expiry the
+    /// runner is killed and the run is reported as a fuzzer error, not a hang. The
+    /// libFuzzer-ABI engines' fuzz runs are not bounded by it.
+    #[arg(long, default_value_t = MAX_FUZZ_TIME)]
+    pub fuzz_timeout: u64,
+    /// Per-stage timeout (seconds) for the coverage stage. The coverage stage does not read it
+    /// and runs without a deadline.
+    #[arg(long, default_value_t = EXECUTION_TIMEOUT)]
+    pub coverage_timeout: u64,
 }
 
 impl Config {
@@ -235,6 +279,16 @@ impl Config {
             fuzzer_run: false,
             disable_power_schedule: false,
             query_budget: 5.00,
+            reject_invalid_input: false,
+            engine: FuzzEngine::LibFuzzer,
+            extra_engines: Vec::new(),
+            disable_dict_gen: false,
+            replay: None,
+            syntax_timeout: 30,
+            link_timeout: 60,
+            execute_timeout: EXECUTION_TIMEOUT,
+            fuzz_timeout: MAX_FUZZ_TIME,
+            coverage_timeout: EXECUTION_TIMEOUT,
         };
         let _ = CONFIG_INSTANCE.set(RwLock::new(config));
         crate::init_debug_logger().unwrap();
@@ -274,6 +328,9 @@ pub struct LibConfig {
     pub disable_fmemopen: Option<bool>,
     /// Memory limit passed to libfuzzer
     pub rss_limit_mb: Option<usize>,
+    /// For codec libraries, the encode/decode API pair that the driver should round-trip the
+    /// input through, asserting the reconstructed buffer matches the original.
+    pub roundtrip: Option<RoundTripSpec>,
 }
 
 impl LibConfig {
@@ -285,6 +342,28 @@ impl LibConfig {
     }
 }
 
+/// Names the encode/decode API pair and comparison strategy used to synthesize a round-trip
+/// (`decode(encode(x)) == x`) driver for a codec library, e.g. zlib, libpng or libjpeg-turbo.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct RoundTripSpec {
+    /// The API that compresses/encodes the raw object, e.g. `compress` or `png_image_write_to_memory`.
+    pub encode_api: String,
+    /// The API that decompresses/decodes the encoded bytes back into the raw object.
+    pub decode_api: String,
+    /// How the reconstructed buffer should be compared against the original input.
+    pub comparison: RoundTripComparison,
+}
+
+/// The comparison performed between the round-tripped buffer and the original input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum RoundTripComparison {
+    /// `decode(encode(x))` must equal `x` byte-for-byte.
+    ExactBytes,
+    /// `decode(encode(x))` must only match the length of `x` (for codecs that are lossy but
+    /// must preserve length, e.g. fixed-block transforms).
+    LengthOnly,
+}
+
 /// Template of generative prompt in system role.
 pub const SYSTEM_GEN_TEMPLATE: &str = "Act as a C++ langauge Developer, write a fuzz driver that follow user's instructions.
 The prototype of fuzz dirver is: `extern \"C\" int LLVMFuzzerTestOneInput(const uint8_t data, size_t size)`.
@@ -324,9 +403,61 @@ pub const USER_GEN_TEMPLATE: &str = "Create a C++ language program step by step
 7. Release all allocated resources before return.
 ";
 
+/// Appended to [`USER_GEN_TEMPLATE`] when [`Config::reject_invalid_input`] is enabled. Teaches
+/// the LLM libFuzzer's input-rejection convention: `return -1` keeps malformed inputs that
+/// cannot meaningfully reach the {project} APIs out of the corpus, while `return 0` keeps ones
+/// that were actually exercised. Its leading `{item}` is renumbered by [`number_items`] so it
+/// continues [`USER_GEN_TEMPLATE`]'s list regardless of what else is appended alongside it.
+pub const USER_REJECT_INPUT_TEMPLATE: &str = "{item}. Before consuming `data`, validate any minimal size or precondition the {project} APIs require. If the input cannot satisfy it, `return -1` immediately so libFuzzer rejects it from the corpus. Otherwise exercise the APIs with the input and `return 0` once they have been meaningfully driven.
+";
+
+/// System prompt variant used in place of [`SYSTEM_GEN_TEMPLATE`] when the target's
+/// [`LibConfig::roundtrip`] is set, steering the LLM towards a differential correctness driver
+/// instead of plain API-sequence exercising.
+pub const SYSTEM_ROUNDTRIP_TEMPLATE: &str = "Act as a C++ langauge Developer, write a fuzz driver that follow user's instructions.
+The prototype of fuzz dirver is: `extern \"C\" int LLVMFuzzerTestOneInput(const uint8_t data, size_t size)`.
+The driver should check a round-trip property instead of merely calling APIs: encode the input then decode the result and confirm it reproduces the original input.
+\n";
+
+/// Appended to the user prompt when [`LibConfig::roundtrip`] is set, naming the concrete
+/// encode/decode API pair the LLM should use and how the reconstructed buffer is compared. Its
+/// four leading `{item}`s are renumbered by [`number_items`], so they continue the list
+/// regardless of what else is appended alongside them.
+pub const USER_ROUNDTRIP_TEMPLATE: &str = "{item}. Treat `data` and `size` as the raw {project} object to round-trip.
+{item}. Call `{encode_api}` to encode/compress the raw object into an intermediate buffer.
+{item}. Call `{decode_api}` to decode/decompress that intermediate buffer back into a reconstructed buffer.
+{item}. Compare the reconstructed buffer against the original `data`/`size` ({comparison}). If they differ, call `abort()` so libFuzzer records the mismatch as a crash.
+";
+
+/// Replace each `{item}` placeholder in `template`, in order of appearance, with sequential
+/// numbers starting at `start`. Returns the rendered template and the next unused number, so
+/// callers appending several numbered sections can keep a single running list instead of each
+/// section hardcoding (and potentially colliding on) its own starting number.
+fn number_items(template: &str, start: usize) -> (String, usize) {
+    let mut n = start;
+    let mut rendered = String::new();
+    let mut rest = template;
+    while let Some(pos) = rest.find("{item}") {
+        rendered.push_str(&rest[..pos]);
+        rendered.push_str(&n.to_string());
+        n += 1;
+        rest = &rest[pos + "{item}".len()..];
+    }
+    rendered.push_str(rest);
+    (rendered, n)
+}
+
 pub fn get_sys_gen_template() -> &'static str {
     pub static TEMPLATE: OnceCell<String> = OnceCell::new();
-    TEMPLATE.get_or_init(|| SYSTEM_GEN_TEMPLATE.to_string())
+    TEMPLATE.get_or_init(|| {
+        let library_name = get_library_name();
+        let deopt = Deopt::new(library_name).unwrap();
+        if deopt.config.roundtrip.is_some() {
+            SYSTEM_ROUNDTRIP_TEMPLATE.to_string()
+        } else {
+            SYSTEM_GEN_TEMPLATE.to_string()
+        }
+    })
 }
 
 pub fn get_user_gen_template() -> &'static str {
@@ -342,6 +473,27 @@ pub fn get_user_chat_template() -> String {
     let library_name = get_library_name();
     let deopt = Deopt::new(library_name).unwrap();
     let mut template = get_user_gen_template().to_string();
+    // USER_GEN_TEMPLATE's own list runs 1-7; continue numbering from there so the
+    // reject-invalid and round-trip sections never both claim the same item number.
+    let mut next_item = 8;
+    if get_config().reject_invalid_input {
+        let (reject, n) = number_items(USER_REJECT_INPUT_TEMPLATE, next_item);
+        next_item = n;
+        template.push_str(&reject.replace("{project}", &deopt.config.project_name));
+    }
+    if let Some(roundtrip) = &deopt.config.roundtrip {
+        let comparison = match roundtrip.comparison {
+            RoundTripComparison::ExactBytes => "must match byte-for-byte",
+            RoundTripComparison::LengthOnly => "must match in length only",
+        };
+        let (roundtrip_instructions, _) = number_items(USER_ROUNDTRIP_TEMPLATE, next_item);
+        let roundtrip_instructions = roundtrip_instructions
+            .replace("{project}", &deopt.config.project_name)
+            .replace("{encode_api}", &roundtrip.encode_api)
+            .replace("{decode_api}", &roundtrip.decode_api)
+            .replace("{comparison}", comparison);
+        template.push_str(&roundtrip_instructions);
+    }
     if let Some(landmark) = deopt.get_library_landmark_corpus() {
         template.insert_str(0, &format!("The input data is: {landmark}\n\n\n."));
     }
@@ -358,3 +510,24 @@ pub fn get_user_chat_template() -> String {
     }
     template
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_number_items_renumbers_sequentially_from_start() {
+        let (rendered, next) = number_items("{item}. a\n{item}. b\n{item}. c\n", 8);
+        assert_eq!(rendered, "8. a\n9. b\n10. c\n");
+        assert_eq!(next, 11);
+    }
+
+    #[test]
+    fn test_number_items_continues_across_two_templates() {
+        let (first, next) = number_items(USER_REJECT_INPUT_TEMPLATE, 8);
+        assert!(first.starts_with("8. "));
+        let (second, _) = number_items(USER_ROUNDTRIP_TEMPLATE, next);
+        assert!(second.starts_with("9. "));
+        assert!(!second.contains("8. "));
+    }
+}
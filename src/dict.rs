@@ -0,0 +1,239 @@
+//! Auto-generated libFuzzer dictionaries.
+//!
+//! PromptFuzz already collects `{headers}`, `{APIs}` and `{context}` (custom types) to fill
+//! [`crate::config::SYSTEM_CONTEXT_TEMPLATE`]. This module mines that same material for string
+//! literals, `#define`d magic tokens, enum constant names and multi-byte integer magic numbers
+//! (e.g. file signatures), and emits them as a libFuzzer `-dict=` file
+//! (<https://llvm.org/docs/LibFuzzer.html#dictionaries>). Dictionaries measurably speed up
+//! coverage on format-parsing libraries by biasing mutation towards tokens the library actually
+//! compares against.
+//!
+//! `Executor::is_program_fuzz_correct` calls [`write_library_dict`] on every call (unless
+//! `Config::disable_dict_gen` is set) as part of its corpus setup, rewriting the target's
+//! dictionary file each time and mining whatever header text is
+//! available in this crate snapshot. [`dict_launch_arg`]'s flag is still not appended to the
+//! launched fuzzer's argv: `execute_fuzzer` is defined outside this snapshot and its signature
+//! has no room for extra argv, so the dictionary is generated and persisted but not yet consumed
+//! by a running fuzzer.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use eyre::Result;
+
+use crate::Deopt;
+
+/// Cap the number of emitted entries so the dictionary stays small enough for libFuzzer to
+/// load quickly and for mutation to still explore outside of it.
+pub const MAX_DICT_ENTRIES: usize = 200;
+
+/// A single extracted token, before it is escaped into libFuzzer dictionary syntax. Holds raw
+/// bytes rather than a `String` because a hex magic-number literal (e.g. a PNG file signature)
+/// is arbitrary binary data, not necessarily valid UTF-8.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum DictToken {
+    /// A quoted string literal, `#define`d string token, or hex magic-number literal found in a
+    /// header.
+    Bytes(Vec<u8>),
+    /// An enum constant name (bare identifier).
+    Ident(String),
+}
+
+/// Scan `headers`, `apis` and `context` for candidate dictionary tokens and render them into
+/// libFuzzer's dictionary file syntax, one `name="value"` entry per line.
+pub fn generate_dict(headers: &str, apis: &str, context: &str) -> String {
+    let mut seen = HashSet::new();
+    let mut tokens = Vec::new();
+    for src in [headers, apis, context] {
+        for token in extract_tokens(src) {
+            if seen.insert(token.clone()) {
+                tokens.push(token);
+            }
+        }
+    }
+    tokens.truncate(MAX_DICT_ENTRIES);
+
+    let mut dict = String::new();
+    for (i, token) in tokens.iter().enumerate() {
+        let value: &[u8] = match token {
+            DictToken::Bytes(b) => b,
+            DictToken::Ident(s) => s.as_bytes(),
+        };
+        dict.push_str(&format!("kw{i}=\"{}\"\n", escape_dict_value(value)));
+    }
+    dict
+}
+
+/// Extract quoted string literals, `#define` tokens and enum constant names from a single
+/// source blob. This is a lightweight lexical scan, not a real C preprocessor/parser, which is
+/// sufficient since dictionary entries are only a mutation hint, not a correctness requirement.
+fn extract_tokens(src: &str) -> Vec<DictToken> {
+    let mut tokens = Vec::new();
+    for line in src.lines() {
+        let line = line.trim();
+        // `#define NAME "value"` or `#define NAME 0x89`
+        if let Some(rest) = line.strip_prefix("#define") {
+            let mut parts = rest.split_whitespace();
+            let _name = parts.next();
+            if let Some(value) = parts.next() {
+                if value.starts_with('"') {
+                    if let Some(s) = extract_quoted(value) {
+                        tokens.push(DictToken::Bytes(s.into_bytes()));
+                    }
+                } else if value.starts_with("0x") || value.starts_with("0X") {
+                    tokens.push(DictToken::Bytes(hex_literal_to_bytes(value)));
+                }
+            }
+        }
+        // any quoted string literal appearing in the line, e.g. magic signatures in comments
+        // or initializers.
+        let mut rest = line;
+        while let Some(start) = rest.find('"') {
+            rest = &rest[start..];
+            if let Some(s) = extract_quoted(rest) {
+                if !s.is_empty() {
+                    tokens.push(DictToken::Bytes(s.clone().into_bytes()));
+                }
+                rest = &rest[(s.len() + 2).min(rest.len())..];
+            } else {
+                break;
+            }
+        }
+        // enum constant declarations: `NAME,` or `NAME = value,` inside an `enum { ... }` body.
+        if let Some(name) = extract_enum_constant(line) {
+            tokens.push(DictToken::Ident(name));
+        }
+    }
+    tokens
+}
+
+/// Parse the leading `"..."` quoted literal from `s`, returning its contents up to the next `"`.
+/// Escape sequences are not processed, so an escaped `\"` ends the literal early.
+fn extract_quoted(s: &str) -> Option<String> {
+    let s = s.strip_prefix('"')?;
+    let end = s.find('"')?;
+    Some(s[..end].to_string())
+}
+
+/// Best-effort match of an enum-constant line: a bare uppercase-leaning identifier optionally
+/// followed by `= <value>` and a trailing comma.
+fn extract_enum_constant(line: &str) -> Option<String> {
+    let line = line.trim_end_matches(',').trim();
+    let name = line.split('=').next()?.trim();
+    if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return None;
+    }
+    let first = name.chars().next()?;
+    if !first.is_ascii_uppercase() && first != '_' {
+        return None;
+    }
+    if name.len() < 3 {
+        return None;
+    }
+    Some(name.to_string())
+}
+
+/// Convert a `0x...` magic-number literal into its raw big-endian byte sequence, e.g. a 4-byte
+/// file-signature constant. Returns raw bytes rather than a `String`: bytes >= 0x80 are not
+/// valid UTF-8 on their own, and round-tripping them through `char`/`String` would re-encode
+/// them as multi-byte UTF-8 sequences, corrupting the literal value.
+fn hex_literal_to_bytes(lit: &str) -> Vec<u8> {
+    let digits: String = lit
+        .trim_start_matches("0x")
+        .trim_start_matches("0X")
+        .chars()
+        .take_while(|c| c.is_ascii_hexdigit())
+        .collect();
+    let digits = if digits.len() % 2 == 1 {
+        format!("0{digits}")
+    } else {
+        digits
+    };
+    let chars: Vec<char> = digits.chars().collect();
+    chars
+        .chunks(2)
+        .filter_map(|pair| {
+            let byte_str: String = pair.iter().collect();
+            u8::from_str_radix(&byte_str, 16).ok()
+        })
+        .collect()
+}
+
+/// Escape a raw token value into libFuzzer's dictionary string syntax: backslashes and quotes
+/// are escaped, and any non-printable byte is hex-escaped as `\xNN`.
+fn escape_dict_value(value: &[u8]) -> String {
+    let mut escaped = String::new();
+    for &byte in value {
+        match byte {
+            b'"' => escaped.push_str("\\\""),
+            b'\\' => escaped.push_str("\\\\"),
+            0x20..=0x7e => escaped.push(byte as char),
+            _ => escaped.push_str(&format!("\\x{byte:02x}")),
+        }
+    }
+    escaped
+}
+
+/// Generate the dictionary for `deopt`'s target and write it next to its shared corpus
+/// directory, overwriting any dictionary written there before.
+pub fn write_library_dict(deopt: &Deopt, headers: &str, apis: &str, context: &str) -> Result<PathBuf> {
+    let dict = generate_dict(headers, apis, context);
+    let dict_path = deopt.get_library_dict_file_name()?;
+    std::fs::write(&dict_path, dict)?;
+    Ok(dict_path)
+}
+
+/// The `-dict=<path>` argv fragment to pass to libFuzzer, if the dictionary was generated.
+pub fn dict_launch_arg(dict_path: &Path) -> String {
+    format!("-dict={}", dict_path.display())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hex_literal_to_bytes_preserves_high_bytes() {
+        // PNG's 8-byte signature: bytes >= 0x80 must survive unchanged, not get re-encoded as
+        // multi-byte UTF-8.
+        let bytes = hex_literal_to_bytes("0x89504E470D0A1A0A");
+        assert_eq!(
+            bytes,
+            vec![0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a]
+        );
+    }
+
+    #[test]
+    fn test_hex_literal_to_bytes_pads_odd_digit_count() {
+        assert_eq!(hex_literal_to_bytes("0xABC"), vec![0x0a, 0xbc]);
+    }
+
+    #[test]
+    fn test_escape_dict_value_escapes_quotes_backslashes_and_high_bytes() {
+        let escaped = escape_dict_value(b"a\"b\\c\x89d");
+        assert_eq!(escaped, "a\\\"b\\\\c\\x89d");
+    }
+
+    #[test]
+    fn test_extract_quoted() {
+        assert_eq!(extract_quoted("\"hello\" world"), Some("hello".to_string()));
+        assert_eq!(extract_quoted("no quote here"), None);
+    }
+
+    #[test]
+    fn test_extract_enum_constant() {
+        assert_eq!(extract_enum_constant("FOO_BAR,"), Some("FOO_BAR".to_string()));
+        assert_eq!(extract_enum_constant("FOO_BAR = 1,"), Some("FOO_BAR".to_string()));
+        assert_eq!(extract_enum_constant("int x = 1;"), None);
+        assert_eq!(extract_enum_constant("ab,"), None);
+    }
+
+    #[test]
+    fn test_generate_dict_dedups_entries() {
+        let headers = r#"#define MAGIC 0x89504E47
+"duplicate"
+"duplicate""#;
+        let dict = generate_dict(headers, "", "");
+        assert_eq!(dict.lines().count(), 2);
+    }
+}
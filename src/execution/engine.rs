@@ -0,0 +1,203 @@
+//! Pluggable fuzzing-engine backends.
+//!
+//! [`crate::config::FUZZER_FLAGS`], [`crate::config::COVERAGE_FLAGS`],
+//! [`crate::config::SANITIZER_FLAGS`] and [`crate::config::ASAN_OPTIONS`] only describe Clang's
+//! built-in `-fsanitize=fuzzer` libFuzzer runtime. [`FuzzEngine`] lets `Config::engine` and
+//! `Config::extra_engines` select additional backends (AFL++, honggfuzz, or the LibAFL
+//! `libafl_libfuzzer` drop-in) that still accept the same `LLVMFuzzerTestOneInput` entrypoint, so
+//! driver generation is unaffected and only the compile, link and launch wiring changes. The
+//! [`FuzzerBackend`] trait is the seam `Executor::is_program_fuzz_correct` drives multiple
+//! engines through, one after another, each on its own per-engine corpus directory.
+//!
+//! `Executor::is_program_fuzz_correct`/`is_program_coverage_correct`/`evolve_corpus` read
+//! [`FuzzerBackend::corpus_subdir`] to pick each engine's corpus directory.
+//! `is_program_link_correct` builds every engine's binary directly via clang with its own
+//! `fuzzer_flags`/`link_args`, and `is_program_execute_correct`/`is_program_fuzz_correct`/
+//! `is_program_coverage_correct` do the same for any non-default engine with its
+//! `sanitizer_flags`/`fuzzer_flags`/`coverage_flags` (`compile`/`Compile` only know the default
+//! libFuzzer-oriented constants, so those stages use them for the default engine only). A
+//! genuinely external engine (AFL++, honggfuzz; see [`FuzzerBackend::is_external_runner`])
+//! is launched through its own runner binary with `run_env` applied, instead of `execute_fuzzer`'s
+//! direct-invocation libFuzzer CLI.
+
+use crate::config::{ASAN_OPTIONS, COVERAGE_FLAGS, FUZZER_FLAGS, SANITIZER_FLAGS};
+
+/// The fuzzing engine used to build and drive a generated harness.
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, clap::ValueEnum, serde::Serialize, serde::Deserialize,
+)]
+pub enum FuzzEngine {
+    /// Clang's built-in `-fsanitize=fuzzer` libFuzzer runtime.
+    #[default]
+    LibFuzzer,
+    /// AFL++'s instrumentation and `afl-fuzz` runner.
+    #[value(name = "aflpp")]
+    AflPlusPlus,
+    /// The LibAFL `libafl_libfuzzer` drop-in runtime, linked in place of libFuzzer.
+    LibAfl,
+    /// Google's honggfuzz, driven through its `honggfuzz` runner.
+    Honggfuzz,
+}
+
+/// The compile flags, link wiring and run-time shape a fuzzing engine needs, so the executor
+/// can drive any number of engines over the same generated harness without hardwiring
+/// libFuzzer. [`FuzzEngine`] is the only implementor today.
+pub trait FuzzerBackend {
+    /// Compile flags for a harness that will be driven interactively by this engine.
+    fn fuzzer_flags(&self) -> Vec<&'static str>;
+    /// Compile flags for a harness built for coverage-guided sanitization.
+    fn coverage_flags(&self) -> Vec<&'static str>;
+    /// Compile flags used by the plain syntax/link sanitization pass.
+    fn sanitizer_flags(&self) -> Vec<&'static str>;
+    /// Extra `-l`/`-Wl` link arguments required to pull in this engine's runtime.
+    fn link_args(&self) -> Vec<&'static str>;
+    /// The environment variables that should be set when launching the compiled harness.
+    fn run_env(&self) -> Vec<(&'static str, String)>;
+    /// Whether this engine is launched through an external runner binary (e.g. `afl-fuzz`,
+    /// `honggfuzz`) rather than invoking the compiled harness directly, as libFuzzer and
+    /// libFuzzer-ABI backends are.
+    fn is_external_runner(&self) -> bool;
+    /// The subdirectory name (under the seed's working directory) this engine's per-engine
+    /// corpus is kept in, so concurrently-run engines don't clobber each other's corpora before
+    /// they are cross-pollinated back into the shared corpus.
+    fn corpus_subdir(&self) -> &'static str;
+}
+
+impl FuzzerBackend for FuzzEngine {
+    fn fuzzer_flags(&self) -> Vec<&'static str> {
+        match self {
+            FuzzEngine::LibFuzzer => FUZZER_FLAGS.to_vec(),
+            FuzzEngine::AflPlusPlus => {
+                // AFL++ instruments via its own compiler wrapper; libFuzzer's `-fsanitize=fuzzer`
+                // is dropped in favor of the standard AFL persistent-mode entry shim.
+                vec![
+                    "-O1",
+                    "-g",
+                    "-fsanitize=address,undefined",
+                    "-ftrivial-auto-var-init=zero",
+                    "-enable-trivial-auto-var-init-zero-knowing-it-will-be-removed-from-clang",
+                ]
+            }
+            FuzzEngine::LibAfl => {
+                // libafl_libfuzzer is a static drop-in for libFuzzer's runtime, so the compile
+                // flags are identical; only the linked library differs (see `link_args`).
+                FUZZER_FLAGS.to_vec()
+            }
+            FuzzEngine::Honggfuzz => vec![
+                "-O1",
+                "-g",
+                "-fsanitize=address,undefined",
+                "-fsanitize-coverage=trace-pc-guard",
+                "-ftrivial-auto-var-init=zero",
+                "-enable-trivial-auto-var-init-zero-knowing-it-will-be-removed-from-clang",
+            ],
+        }
+    }
+
+    fn coverage_flags(&self) -> Vec<&'static str> {
+        match self {
+            FuzzEngine::LibFuzzer | FuzzEngine::LibAfl => COVERAGE_FLAGS.to_vec(),
+            FuzzEngine::AflPlusPlus | FuzzEngine::Honggfuzz => {
+                let mut flags = COVERAGE_FLAGS.to_vec();
+                flags.retain(|f| *f != "-fsanitize=fuzzer");
+                flags
+            }
+        }
+    }
+
+    fn sanitizer_flags(&self) -> Vec<&'static str> {
+        match self {
+            FuzzEngine::LibFuzzer | FuzzEngine::LibAfl => SANITIZER_FLAGS.to_vec(),
+            FuzzEngine::AflPlusPlus | FuzzEngine::Honggfuzz => {
+                let mut flags = SANITIZER_FLAGS.to_vec();
+                flags.retain(|f| *f != "-fsanitize=fuzzer");
+                flags
+            }
+        }
+    }
+
+    fn link_args(&self) -> Vec<&'static str> {
+        match self {
+            FuzzEngine::LibFuzzer => vec![],
+            FuzzEngine::AflPlusPlus => vec![],
+            FuzzEngine::LibAfl => vec!["-lafl_libfuzzer_runtime", "-lstdc++"],
+            FuzzEngine::Honggfuzz => vec!["-lhfuzz"],
+        }
+    }
+
+    fn run_env(&self) -> Vec<(&'static str, String)> {
+        let asan_options = ("ASAN_OPTIONS", ASAN_OPTIONS.join(":"));
+        match self {
+            FuzzEngine::LibFuzzer | FuzzEngine::LibAfl => vec![asan_options],
+            FuzzEngine::AflPlusPlus => vec![
+                ("AFL_SKIP_CPUFREQ", "1".to_string()),
+                ("AFL_NO_AFFINITY", "1".to_string()),
+                asan_options,
+            ],
+            FuzzEngine::Honggfuzz => vec![asan_options],
+        }
+    }
+
+    fn is_external_runner(&self) -> bool {
+        matches!(self, FuzzEngine::AflPlusPlus | FuzzEngine::Honggfuzz)
+    }
+
+    fn corpus_subdir(&self) -> &'static str {
+        match self {
+            FuzzEngine::LibFuzzer => "corpus",
+            FuzzEngine::AflPlusPlus => "corpus.aflpp",
+            FuzzEngine::LibAfl => "corpus.libafl",
+            FuzzEngine::Honggfuzz => "corpus.honggfuzz",
+        }
+    }
+}
+
+/// The set of engines a fuzzing round should drive: the primary [`FuzzEngine`] plus any extra
+/// engines configured to run on the same harness, each with its own corpus directory. The
+/// primary engine comes first and duplicates are dropped.
+pub fn active_engines(primary: FuzzEngine, extra: &[FuzzEngine]) -> Vec<FuzzEngine> {
+    let mut engines = vec![primary];
+    for engine in extra {
+        if !engines.contains(engine) {
+            engines.push(*engine);
+        }
+    }
+    engines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_active_engines_dedups_and_keeps_primary_first() {
+        let engines = active_engines(
+            FuzzEngine::LibFuzzer,
+            &[FuzzEngine::AflPlusPlus, FuzzEngine::LibFuzzer, FuzzEngine::Honggfuzz],
+        );
+        assert_eq!(
+            engines,
+            vec![FuzzEngine::LibFuzzer, FuzzEngine::AflPlusPlus, FuzzEngine::Honggfuzz]
+        );
+    }
+
+    #[test]
+    fn test_active_engines_with_no_extras_is_just_primary() {
+        assert_eq!(active_engines(FuzzEngine::LibAfl, &[]), vec![FuzzEngine::LibAfl]);
+    }
+
+    #[test]
+    fn test_corpus_subdir_is_distinct_per_engine() {
+        let subdirs: HashSet<&str> = [
+            FuzzEngine::LibFuzzer,
+            FuzzEngine::AflPlusPlus,
+            FuzzEngine::LibAfl,
+            FuzzEngine::Honggfuzz,
+        ]
+        .iter()
+        .map(|e| e.corpus_subdir())
+        .collect();
+        assert_eq!(subdirs.len(), 4);
+    }
+}
@@ -0,0 +1,106 @@
+//! Bounding subprocess work by a deadline.
+//!
+//! The sanitization pipeline spawns `clang++`, the compiled binary, and the fuzzer without any
+//! time bound, so a pathological generated program can stall a whole core indefinitely.
+//! Following cargo-test-fuzz's use of a `Duration` timeout around subprocess execution,
+//! [`run_command_with_timeout`] polls a spawned `Command` until it exits or a deadline passes,
+//! killing its whole process group on expiry so a timed-out stage surfaces as a
+//! [`super::logger::ProgramError::Hang`] instead of hanging `concurrent_check_batch` forever.
+//! `is_program_syntax_correct` and `is_program_link_correct` are wired through this, bounded by
+//! `Config::syntax_timeout` and `Config::link_timeout` respectively, and `is_program_fuzz_correct`
+//! bounds any genuinely external engine (AFL++, honggfuzz) by `Config::fuzz_timeout` (a timed-out
+//! external engine is reported as a `ProgramError::Fuzzer`, not a `Hang`). The
+//! execute and coverage stages, and the libFuzzer-ABI engines' own fuzz runs, still call straight
+//! through to `execute_pool`/`collect_code_coverage`/`execute_fuzzer`, which are defined outside
+//! this crate snapshot and run their subprocesses directly rather than through this module, so
+//! `Config::execute_timeout`/`Config::coverage_timeout` are not enforced here (libFuzzer's own
+//! `-max_total_time`/`-timeout` flags bound the libFuzzer-ABI fuzz runs instead).
+
+use std::io::Read;
+use std::process::{Child, Command, Output, Stdio};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+#[cfg(unix)]
+use std::os::unix::process::CommandExt;
+
+use eyre::Result;
+
+/// The outcome of bounding a child process or stage by a deadline.
+pub enum Bounded<T> {
+    /// The work finished within the deadline.
+    Done(T),
+    /// The deadline elapsed before the work finished.
+    TimedOut,
+}
+
+/// Spawn `command`, capturing stdout/stderr concurrently on dedicated threads so a verbose
+/// sanitizer doesn't deadlock on a full pipe buffer, and poll for exit until `timeout` elapses.
+/// On expiry the whole process group is killed (so a clang invocation that forked helper
+/// processes, or a fuzzer that forked a crashing child, doesn't survive its parent) and
+/// [`Bounded::TimedOut`] is returned.
+pub fn run_command_with_timeout(mut command: Command, timeout: Duration) -> Result<Bounded<Output>> {
+    #[cfg(unix)]
+    command.process_group(0);
+
+    let mut child: Child = command
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    let stdout = child.stdout.take();
+    let stderr = child.stderr.take();
+    let (out_tx, out_rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        let mut buf = Vec::new();
+        if let Some(mut stdout) = stdout {
+            let _ = stdout.read_to_end(&mut buf);
+        }
+        let _ = out_tx.send(buf);
+    });
+    let (err_tx, err_rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        let mut buf = Vec::new();
+        if let Some(mut stderr) = stderr {
+            let _ = stderr.read_to_end(&mut buf);
+        }
+        let _ = err_tx.send(buf);
+    });
+
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if Instant::now() >= deadline {
+            kill_process_group(&child);
+            let _ = child.wait();
+            return Ok(Bounded::TimedOut);
+        }
+        std::thread::sleep(Duration::from_millis(20));
+    };
+
+    let stdout = out_rx.recv_timeout(Duration::from_secs(5)).unwrap_or_default();
+    let stderr = err_rx.recv_timeout(Duration::from_secs(5)).unwrap_or_default();
+    Ok(Bounded::Done(Output {
+        status,
+        stdout,
+        stderr,
+    }))
+}
+
+/// Kill `child`'s whole process group, not just the immediate child, so grandchildren it forked
+/// (a shell wrapping clang, a sanitizer's forked worker) don't outlive a timed-out stage.
+/// `process_group(0)` above put the child in its own group whose id equals its pid, so signaling
+/// `-pid` reaches the whole group.
+#[cfg(unix)]
+fn kill_process_group(child: &Child) {
+    unsafe {
+        libc::kill(-(child.id() as i32), libc::SIGKILL);
+    }
+}
+
+#[cfg(not(unix))]
+fn kill_process_group(child: &Child) {
+    let _ = child;
+}
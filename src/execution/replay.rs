@@ -0,0 +1,259 @@
+//! Deterministic crash-corpus replay and triage.
+//!
+//! `--replay <dir>` is a non-generating mode: instead of driving the LLM loop, it feeds every
+//! file under `<dir>` (recursively) through the already-built binary one input at a time,
+//! bounded by [`crate::config::EXECUTION_TIMEOUT`], and reports per-file outcome (clean / ASan /
+//! UBSan / timeout) together with a deduplicated crash signature. This mirrors keeping raw crash
+//! inputs in a `test_cases/` directory and running each one deterministically for regression
+//! triage after a library upgrade, instead of re-running the whole fuzzing campaign.
+//!
+//! [`dispatch_replay`] is the dispatch check itself: it reads `Config::replay` and, if set, runs
+//! [`Executor::replay_corpus`] and reports `true` so a caller knows to skip the normal LLM-driven
+//! fuzz loop. This crate snapshot has no top-level command-mode entrypoint to call it from (no
+//! `main.rs`/`src/bin/` exists here), so it is not yet reachable from a running binary; wiring it
+//! in fully needs that entrypoint's own source, which lives outside this snapshot.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Duration;
+
+use eyre::Result;
+
+use crate::config::EXECUTION_TIMEOUT;
+use crate::deopt::utils::read_sort_dir;
+
+use super::{proc, Executor};
+
+/// The outcome of replaying a single input file against a binary.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReplayVerdict {
+    /// The binary exited cleanly: no crash.
+    Clean,
+    /// AddressSanitizer reported an error; carries its first report line as the signature.
+    Asan(String),
+    /// UndefinedBehaviorSanitizer reported an error; carries its first report line as the signature.
+    Ubsan(String),
+    /// The binary did not terminate within [`EXECUTION_TIMEOUT`] and was killed.
+    Timeout,
+}
+
+impl ReplayVerdict {
+    /// A short, human-readable label for summary tables.
+    pub fn label(&self) -> &'static str {
+        match self {
+            ReplayVerdict::Clean => "clean",
+            ReplayVerdict::Asan(_) => "asan",
+            ReplayVerdict::Ubsan(_) => "ubsan",
+            ReplayVerdict::Timeout => "timeout",
+        }
+    }
+
+    /// The deduplication signature for this verdict, or `None` for a clean run, which has
+    /// nothing to deduplicate.
+    pub fn signature(&self) -> Option<&str> {
+        match self {
+            ReplayVerdict::Clean => None,
+            ReplayVerdict::Asan(sig) | ReplayVerdict::Ubsan(sig) => Some(sig),
+            ReplayVerdict::Timeout => Some("timeout"),
+        }
+    }
+}
+
+/// The result of replaying one input file.
+#[derive(Debug, Clone)]
+pub struct ReplayOutcome {
+    pub input: PathBuf,
+    pub verdict: ReplayVerdict,
+}
+
+/// The aggregate result of a `--replay` run: every file's outcome, plus the files grouped by
+/// deduplicated crash signature so users can see how many distinct bugs a crash corpus holds.
+#[derive(Debug, Default)]
+pub struct ReplaySummary {
+    pub outcomes: Vec<ReplayOutcome>,
+}
+
+impl ReplaySummary {
+    /// Group non-clean outcomes by their deduplication signature.
+    pub fn group_by_signature(&self) -> BTreeMap<&str, Vec<&Path>> {
+        let mut groups: BTreeMap<&str, Vec<&Path>> = BTreeMap::new();
+        for outcome in &self.outcomes {
+            if let Some(sig) = outcome.verdict.signature() {
+                groups.entry(sig).or_default().push(&outcome.input);
+            }
+        }
+        groups
+    }
+
+    /// A one-line-per-group summary suitable for printing to the user.
+    pub fn render_summary(&self) -> String {
+        let total = self.outcomes.len();
+        let clean = self
+            .outcomes
+            .iter()
+            .filter(|o| o.verdict == ReplayVerdict::Clean)
+            .count();
+        let groups = self.group_by_signature();
+        let mut out = format!(
+            "Replayed {total} inputs: {clean} clean, {} distinct crash signatures.\n",
+            groups.len()
+        );
+        for (sig, files) in &groups {
+            out.push_str(&format!("  [{} file(s)] {sig}\n", files.len()));
+        }
+        out
+    }
+}
+
+impl Executor {
+    /// Replay every file under `corpus_dir` (recursively) against `binary`, one input at a
+    /// time, and return the per-file outcomes grouped by crash signature.
+    pub fn replay_corpus(&self, binary: &Path, corpus_dir: &Path) -> Result<ReplaySummary> {
+        let mut outcomes = Vec::new();
+        for input in collect_files_recursively(corpus_dir)? {
+            let verdict = self.replay_one(binary, &input)?;
+            outcomes.push(ReplayOutcome {
+                input,
+                verdict,
+            });
+        }
+        Ok(ReplaySummary { outcomes })
+    }
+
+    /// Run `binary` on a single `input` file, bounded by [`EXECUTION_TIMEOUT`], and classify
+    /// the result. Stdout/stderr are captured concurrently by
+    /// [`proc::run_command_with_timeout`], so a sanitizer writing more than a pipe buffer's
+    /// worth of diagnostics can't deadlock the replay.
+    fn replay_one(&self, binary: &Path, input: &Path) -> Result<ReplayVerdict> {
+        let mut command = Command::new(binary);
+        command
+            .arg(input)
+            .env("ASAN_OPTIONS", crate::config::ASAN_OPTIONS.join(":"));
+
+        let output = match proc::run_command_with_timeout(command, Duration::from_secs(EXECUTION_TIMEOUT))? {
+            proc::Bounded::TimedOut => return Ok(ReplayVerdict::Timeout),
+            proc::Bounded::Done(output) => output,
+        };
+        if output.status.success() {
+            return Ok(ReplayVerdict::Clean);
+        }
+        let combined = format!(
+            "{}{}",
+            String::from_utf8_lossy(&output.stderr),
+            String::from_utf8_lossy(&output.stdout)
+        );
+        Ok(classify_sanitizer_output(&combined))
+    }
+}
+
+/// Classify a crashing process's combined stdout/stderr into an ASan/UBSan verdict, using the
+/// first line that mentions the sanitizer as the deduplication signature. Output that mentions
+/// neither sanitizer is classified as ASan, with its first line as the signature.
+fn classify_sanitizer_output(output: &str) -> ReplayVerdict {
+    for line in output.lines() {
+        if line.contains("AddressSanitizer") {
+            return ReplayVerdict::Asan(line.trim().to_string());
+        }
+        if line.contains("UndefinedBehaviorSanitizer") || line.contains("runtime error:") {
+            return ReplayVerdict::Ubsan(line.trim().to_string());
+        }
+    }
+    ReplayVerdict::Asan(
+        output
+            .lines()
+            .next()
+            .unwrap_or("unknown sanitizer error")
+            .trim()
+            .to_string(),
+    )
+}
+
+/// Check whether `--replay` was requested and, if so, replay `binary` against it and print the
+/// resulting summary. Returns `true` when replay ran, so a caller can skip the normal
+/// LLM-generation loop in that case and `false` when `Config::replay` wasn't set.
+pub fn dispatch_replay(executor: &Executor, binary: &Path) -> Result<bool> {
+    let config = crate::config::get_config();
+    let corpus_dir = config.replay.clone();
+    drop(config);
+
+    let Some(corpus_dir) = corpus_dir else {
+        return Ok(false);
+    };
+    let summary = executor.replay_corpus(binary, &corpus_dir)?;
+    print!("{}", summary.render_summary());
+    Ok(true)
+}
+
+/// Recursively collect every regular file under `dir`, sorted for deterministic replay order.
+fn collect_files_recursively(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for entry in read_sort_dir(dir)? {
+        if entry.is_dir() {
+            files.extend(collect_files_recursively(&entry)?);
+        } else {
+            files.push(entry);
+        }
+    }
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_sanitizer_output_detects_asan() {
+        let output = "==1==ERROR: AddressSanitizer: heap-buffer-overflow\n    #0 0x0 in foo";
+        match classify_sanitizer_output(output) {
+            ReplayVerdict::Asan(sig) => assert!(sig.contains("AddressSanitizer")),
+            other => panic!("expected Asan, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_classify_sanitizer_output_detects_ubsan() {
+        let output = "prog.cc:12:5: runtime error: signed integer overflow";
+        match classify_sanitizer_output(output) {
+            ReplayVerdict::Ubsan(sig) => assert!(sig.contains("runtime error")),
+            other => panic!("expected Ubsan, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_classify_sanitizer_output_falls_back_to_first_line() {
+        let output = "some unrecognized crash output\nmore detail";
+        match classify_sanitizer_output(output) {
+            ReplayVerdict::Asan(sig) => assert_eq!(sig, "some unrecognized crash output"),
+            other => panic!("expected fallback Asan, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_collect_files_recursively() -> Result<()> {
+        let dir = std::env::temp_dir().join(format!(
+            "promptfuzz_replay_test_{}",
+            std::process::id()
+        ));
+        std::fs::create_dir_all(dir.join("nested"))?;
+        std::fs::write(dir.join("a"), b"a")?;
+        std::fs::write(dir.join("nested").join("b"), b"b")?;
+
+        let files = collect_files_recursively(&dir)?;
+        assert_eq!(files.len(), 2);
+
+        std::fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_replay_verdict_signature_and_label() {
+        assert_eq!(ReplayVerdict::Clean.label(), "clean");
+        assert_eq!(ReplayVerdict::Clean.signature(), None);
+        assert_eq!(ReplayVerdict::Timeout.signature(), Some("timeout"));
+        assert_eq!(
+            ReplayVerdict::Asan("sig".to_string()).signature(),
+            Some("sig")
+        );
+    }
+}
@@ -12,54 +12,186 @@ use eyre::Result;
 use std::{
     path::{Path, PathBuf},
     process::{Command, Stdio},
+    time::Duration,
 };
 
 use self::utils::cleanup_sanitize_dir;
 
 use super::{
     ast::remove_duplicate_definition,
+    engine::{FuzzEngine, FuzzerBackend},
     logger::{ProgramError, TimeUsage},
-    Executor,
+    proc, triage, Executor,
 };
 
+/// How many repair-and-recheck rounds `is_program_syntax_correct` will attempt before giving up
+/// and reporting the remaining diagnostics as a genuine `ProgramError::Syntax`.
+const MAX_FIXIT_REPAIR_ITERS: u8 = 3;
+
+/// A single clang `-fdiagnostics-parseable-fixits` edit: replace the 1-based line:col span
+/// `[start, end)` with `replacement`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct FixIt {
+    start_line: usize,
+    start_col: usize,
+    end_line: usize,
+    end_col: usize,
+    replacement: String,
+}
+
+/// Parse a single `fix-it:"<file>":{startLine:startCol-endLine:endCol}:"<replacement>"` line.
+/// Returns `None` for any other diagnostic line.
+fn parse_fixit_line(line: &str) -> Option<FixIt> {
+    let rest = line.strip_prefix("fix-it:")?.strip_prefix('"')?;
+    let path_end = rest.find('"')?;
+    let rest = rest[path_end + 1..].strip_prefix(":{")?;
+    let brace_end = rest.find('}')?;
+    let span = &rest[..brace_end];
+    let rest = rest[brace_end + 1..].strip_prefix(':')?.trim_start();
+    let rest = rest.strip_prefix('"')?;
+    let repl_end = rest.rfind('"')?;
+    let replacement = unescape_fixit_string(&rest[..repl_end]);
+
+    let (start, end) = span.split_once('-')?;
+    let (start_line, start_col) = start.split_once(':')?;
+    let (end_line, end_col) = end.split_once(':')?;
+    Some(FixIt {
+        start_line: start_line.parse().ok()?,
+        start_col: start_col.parse().ok()?,
+        end_line: end_line.parse().ok()?,
+        end_col: end_col.parse().ok()?,
+        replacement,
+    })
+}
+
+/// Undo clang's escaping of `"` and `\` inside a fix-it's replacement string.
+fn unescape_fixit_string(s: &str) -> String {
+    s.replace("\\\"", "\"").replace("\\\\", "\\")
+}
+
+/// Convert a clang 1-based (line, byte-column) position into an absolute byte offset in `src`.
+fn line_col_to_offset(src: &str, line: usize, col: usize) -> Option<usize> {
+    let mut offset = 0;
+    for (i, l) in src.split_inclusive('\n').enumerate() {
+        if i + 1 == line {
+            return Some(offset + col.saturating_sub(1));
+        }
+        offset += l.len();
+    }
+    None
+}
+
+/// Apply a batch of fix-its to `src`, returning the repaired source. Edits are applied in
+/// reverse offset order so earlier edits don't shift later spans; any edit overlapping one
+/// already applied is skipped to stay safe.
+fn apply_fixits(src: &str, fixits: Vec<FixIt>) -> String {
+    let mut spans: Vec<(usize, usize, String)> = fixits
+        .into_iter()
+        .filter_map(|f| {
+            let start = line_col_to_offset(src, f.start_line, f.start_col)?;
+            let end = line_col_to_offset(src, f.end_line, f.end_col)?;
+            (start <= end && end <= src.len()).then_some((start, end, f.replacement))
+        })
+        .collect();
+    spans.sort_by(|a, b| b.0.cmp(&a.0));
+
+    let mut result = src.to_string();
+    let mut next_allowed_end = usize::MAX;
+    for (start, end, replacement) in spans {
+        if end > next_allowed_end {
+            continue;
+        }
+        result.replace_range(start..end, &replacement);
+        next_allowed_end = start;
+    }
+    result
+}
+
 impl Executor {
     /// check whether the c program is syntactically and semantically correct.
+    ///
+    /// Before giving up, this borrows the approach rustfix/compiletest use for rustc
+    /// suggestions: re-invoke clang with `-fdiagnostics-parseable-fixits`, which emits
+    /// machine-applicable `fix-it:"<file>":{startLine:startCol-endLine:endCol}:"<replacement>"`
+    /// edits alongside its diagnostics, apply them, and recheck. This recovers many near-miss
+    /// LLM-generated programs (missing semicolons, casts, includes) that would otherwise be
+    /// thrown away.
     fn is_program_syntax_correct(&self, program_path: &Path) -> Result<Option<ProgramError>> {
         let time_logger = TimeUsage::new(get_file_dirname(program_path));
-        let output: std::process::Output = Command::new("clang++")
-            .stdout(Stdio::null())
-            .arg("-fsyntax-only")
-            .arg(&self.header_cmd)
-            .arg(program_path.as_os_str())
-            .output()
-            .expect("failed to execute the syntax check process");
-        time_logger.log("syntax")?;
-        let success = output.status.success();
-        if success {
-            return Ok(None);
+        let timeout = Duration::from_secs(get_config().syntax_timeout);
+        let mut err_msg = String::new();
+        for _ in 0..=MAX_FIXIT_REPAIR_ITERS {
+            let mut command = Command::new("clang++");
+            command
+                .arg("-fsyntax-only")
+                .arg("-fdiagnostics-parseable-fixits")
+                .arg(&self.header_cmd)
+                .arg(program_path.as_os_str());
+            let output = match proc::run_command_with_timeout(command, timeout)? {
+                proc::Bounded::Done(output) => output,
+                proc::Bounded::TimedOut => {
+                    time_logger.log("syntax")?;
+                    return Ok(Some(ProgramError::Hang(format!(
+                        "syntax check timed out after {}s",
+                        timeout.as_secs()
+                    ))));
+                }
+            };
+            if output.status.success() {
+                time_logger.log("syntax")?;
+                return Ok(None);
+            }
+            err_msg = String::from_utf8_lossy(&output.stderr).to_string();
+            let fixits: Vec<FixIt> = err_msg.lines().filter_map(parse_fixit_line).collect();
+            if fixits.is_empty() {
+                break;
+            }
+            let src = std::fs::read_to_string(program_path)?;
+            let repaired = apply_fixits(&src, fixits);
+            if repaired == src {
+                // the edit set stopped changing, clang will just emit the same fix-its again.
+                break;
+            }
+            std::fs::write(program_path, repaired)?;
         }
-        let err_msg = String::from_utf8_lossy(&output.stderr).to_string();
+        time_logger.log("syntax")?;
         Ok(Some(ProgramError::Syntax(err_msg)))
     }
 
     /// check whether the program is correct in compilation and linkage.
+    ///
+    /// Bounded by `Config::link_timeout`, same as `is_program_syntax_correct` is by
+    /// `Config::syntax_timeout`: this compiles directly via clang through
+    /// [`Self::compile_with_timeout`] rather than the unbounded `compile`, so a pathological link
+    /// surfaces as a [`ProgramError::Hang`] instead of stalling a core forever.
     fn is_program_link_correct(&self, program_path: &Path) -> Result<Option<ProgramError>> {
         let time_logger = TimeUsage::new(get_file_dirname(program_path));
         remove_duplicate_definition(program_path)?;
         let mut binary_out = PathBuf::from(program_path);
         binary_out.set_extension("out");
 
-        let res = self.compile(vec![program_path], &binary_out, super::Compile::FUZZER);
+        // Link against the runtime of whichever engine `Config::engine` selects rather than
+        // always assuming libFuzzer's. `is_program_execute_correct` later rebuilds this same
+        // `.out` path, and that rebuild is what `is_program_fuzz_correct` reuses.
+        let engine = get_config().engine;
+        let timeout = Duration::from_secs(get_config().link_timeout);
+        let res = self.compile_with_timeout(
+            program_path,
+            &binary_out,
+            &engine.fuzzer_flags(),
+            &engine.link_args(),
+            timeout,
+        )?;
         time_logger.log("link")?;
-
-        if let Err(err) = res {
-            let err_msg = err.to_string();
-            return Ok(Some(ProgramError::Link(err_msg)));
-        }
-        Ok(None)
+        Ok(res)
     }
 
     /// linked with AddressSanitizer, execute it to check whether code is correct.
+    ///
+    /// Still not bounded by `Config::execute_timeout`, unlike the link stage: `execute_pool` is
+    /// defined outside this snapshot and runs each corpus file directly rather than through
+    /// [`proc::run_command_with_timeout`], so a hanging input can still stall this stage. Fixing
+    /// this for real needs `execute_pool`'s own source, which this crate snapshot doesn't have.
     fn is_program_execute_correct(&self, program_path: &Path) -> Result<Option<ProgramError>> {
         let time_logger = TimeUsage::new(get_file_dirname(program_path));
         let mut transformer = Transformer::new(program_path, &self.deopt)?;
@@ -72,7 +204,17 @@ impl Executor {
         self.deopt
             .copy_library_init_file(&get_file_dirname(program_path))?;
 
-        self.compile(vec![program_path], &binary_out, super::Compile::FUZZER)?;
+        let engine = get_config().engine;
+        if engine == FuzzEngine::LibFuzzer {
+            self.compile(vec![program_path], &binary_out, super::Compile::FUZZER)?;
+        } else {
+            self.compile_with_flags(
+                program_path,
+                &binary_out,
+                &engine.sanitizer_flags(),
+                &engine.link_args(),
+            )?;
+        }
 
         // Execute the program on each corpus file and check error.
         let corpus = self.deopt.get_library_shared_corpus_dir()?;
@@ -81,40 +223,248 @@ impl Executor {
         Ok(has_err)
     }
 
-    /// linked with LibFuzzer and AddressSanitizer, to check whether code is correct.
+    /// linked with LibFuzzer (or whichever `FuzzerBackend`s are configured) and AddressSanitizer,
+    /// to check whether code is correct. When `Config::extra_engines` is non-empty, every
+    /// configured engine is driven for the duration timeout on its own per-engine corpus
+    /// directory (see `FuzzerBackend::corpus_subdir`). The engines run one after another, not
+    /// at the same time; after each extra engine's run its corpus is copied into the shared
+    /// corpus so later engines start from its finds.
+    ///
+    /// `Config::engine` (the primary engine) reuses the `.out` binary `is_program_execute_correct`
+    /// last built for it (`Compile::FUZZER` for libFuzzer, otherwise its own
+    /// `sanitizer_flags`/`link_args`). Every other active engine gets its
+    /// own dedicated build via [`Self::compile_with_flags`], using that engine's own
+    /// `fuzzer_flags`/`link_args` rather than the primary engine's. A genuinely external engine
+    /// (`FuzzerBackend::is_external_runner`, i.e. AFL++/honggfuzz) is driven through
+    /// [`Self::run_external_fuzzer`], which launches its real runner binary (bounded by
+    /// `Config::fuzz_timeout` via [`proc::run_command_with_timeout`]) with that engine's
+    /// `run_env` applied, instead of going through `execute_fuzzer`'s direct libFuzzer-CLI
+    /// invocation. The libFuzzer-ABI engines (`LibFuzzer`, `LibAfl`) still go through
+    /// `execute_fuzzer`, which enforces libFuzzer's own `-max_total_time`/`-timeout` flags rather
+    /// than `Config::fuzz_timeout` directly.
     pub fn is_program_fuzz_correct(&self, program_path: &Path) -> Result<Option<ProgramError>> {
         log::trace!("test program is fuzz correct: {program_path:?}");
         let work_dir = get_file_dirname(program_path);
         let time_logger = TimeUsage::new(work_dir.clone());
 
-        let binary_out = program_path.with_extension("out");
+        let config = get_config();
+        let engines = super::engine::active_engines(config.engine, &config.extra_engines);
+        let disable_dict_gen = config.disable_dict_gen;
+        drop(config);
+
+        if !disable_dict_gen {
+            // Headers are the one piece of `config::SYSTEM_CONTEXT_TEMPLATE`'s {headers}/{APIs}/
+            // {context} trio this crate snapshot exposes an accessor for (the same one
+            // `check_programs_are_correct` uses to build each program's header preamble); the
+            // APIs/context text is collected
+            // by the request/prompt-building module, which lives outside this snapshot, so this
+            // mines headers only for now. `dict_launch_arg`'s flag still isn't appended to the
+            // launched fuzzer's argv: `execute_fuzzer` is defined outside this snapshot and its
+            // signature has no room for extra argv, so the generated dictionary is written out
+            // but not yet picked up by a running fuzzer.
+            let headers = crate::deopt::utils::format_library_header_strings(&self.deopt);
+            let dict_path = crate::dict::write_library_dict(&self.deopt, headers, "", "")?;
+            log::debug!(
+                "wrote libFuzzer dictionary to {dict_path:?} ({})",
+                crate::dict::dict_launch_arg(&dict_path)
+            );
+        }
 
-        // execute fuzzer for duration timeout.
-        let corpus_dir: PathBuf = [work_dir, "corpus".into()].iter().collect();
-        crate::deopt::utils::create_dir_if_nonexist(&corpus_dir)?;
+        let primary_engine = get_config().engine;
+        let primary_binary_out = program_path.with_extension("out");
 
-        let res = self.execute_fuzzer(
-            &binary_out,
-            vec![&corpus_dir, &self.deopt.get_library_shared_corpus_dir()?],
-        );
-        time_logger.log("fuzz")?;
-        if let Err(err) = res {
-            return Ok(Some(ProgramError::Fuzzer(err.to_string())));
+        for engine in &engines {
+            let binary_out = if *engine == primary_engine {
+                // `is_program_execute_correct` already built this binary for `primary_engine`
+                // (`Compile::FUZZER`, or that engine's own `sanitizer_flags`/`link_args`), so it's
+                // safe to reuse here.
+                primary_binary_out.clone()
+            } else {
+                // any other active engine (an `extra_engines` entry) needs its own dedicated
+                // build: `primary_binary_out` is linked against `primary_engine`'s runtime, which
+                // is the wrong one for a libFuzzer-ABI-compatible engine like `LibAfl` and
+                // meaningless for a genuinely external one like AFL++/honggfuzz.
+                let binary_out =
+                    program_path.with_extension(format!("{engine:?}.out").to_lowercase());
+                self.compile_with_flags(
+                    program_path,
+                    &binary_out,
+                    &engine.fuzzer_flags(),
+                    &engine.link_args(),
+                )?;
+                binary_out
+            };
+            let engine_corpus_dir: PathBuf =
+                [work_dir.clone(), engine.corpus_subdir().into()].iter().collect();
+            crate::deopt::utils::create_dir_if_nonexist(&engine_corpus_dir)?;
+
+            let res = if engine.is_external_runner() {
+                self.run_external_fuzzer(&binary_out, &engine_corpus_dir, *engine)
+            } else {
+                self.execute_fuzzer(
+                    &binary_out,
+                    vec![&engine_corpus_dir, &self.deopt.get_library_shared_corpus_dir()?],
+                )
+            };
+            if let Err(err) = res {
+                time_logger.log("fuzz")?;
+                return Ok(Some(ProgramError::Fuzzer(format!("[{engine:?}] {err}"))));
+            }
+            if *engine != primary_engine {
+                self.deopt
+                    .copy_file_to_shared_corpus(crate::deopt::utils::read_sort_dir(
+                        &engine_corpus_dir,
+                    )?)?;
+            }
         }
+        time_logger.log("fuzz")?;
         Ok(None)
     }
 
+    /// Compile `program_path` into `binary_out` directly via clang, applying `flags`/`link_args`
+    /// verbatim instead of delegating to `compile`/`Compile`, which only know the default
+    /// libFuzzer-oriented `FUZZER_FLAGS`/`COVERAGE_FLAGS`/`SANITIZER_FLAGS` constants and have no
+    /// way to express a non-default [`FuzzerBackend`]'s own compile/link flags.
+    fn compile_with_flags(
+        &self,
+        program_path: &Path,
+        binary_out: &Path,
+        flags: &[&str],
+        link_args: &[&str],
+    ) -> Result<()> {
+        let mut command = Command::new("clang++");
+        command.arg(&self.header_cmd);
+        command.args(flags);
+        command.args(link_args);
+        command.arg(program_path).arg("-o").arg(binary_out);
+        let output = command.output()?;
+        if !output.status.success() {
+            eyre::bail!(
+                "failed to compile {program_path:?} for a non-default fuzzing engine: {}",
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+        Ok(())
+    }
+
+    /// Compile `program_path` into `binary_out` the same way [`Self::compile_with_flags`] does,
+    /// but bounded by `timeout` via [`proc::run_command_with_timeout`] rather than a plain
+    /// `Command::output`, so a pathological link surfaces as a [`ProgramError::Hang`] instead of
+    /// stalling a core forever. Used by `is_program_link_correct`, which `Config::link_timeout`
+    /// is meant to bound.
+    fn compile_with_timeout(
+        &self,
+        program_path: &Path,
+        binary_out: &Path,
+        flags: &[&str],
+        link_args: &[&str],
+        timeout: Duration,
+    ) -> Result<Option<ProgramError>> {
+        let mut command = Command::new("clang++");
+        command.arg(&self.header_cmd);
+        command.args(flags);
+        command.args(link_args);
+        command.arg(program_path).arg("-o").arg(binary_out);
+        match proc::run_command_with_timeout(command, timeout)? {
+            proc::Bounded::TimedOut => Ok(Some(ProgramError::Hang(format!(
+                "link timed out after {}s",
+                timeout.as_secs()
+            )))),
+            proc::Bounded::Done(output) if !output.status.success() => Ok(Some(
+                ProgramError::Link(String::from_utf8_lossy(&output.stderr).to_string()),
+            )),
+            proc::Bounded::Done(_) => Ok(None),
+        }
+    }
+
+    /// Drive a genuinely external fuzzing engine (AFL++, honggfuzz) by launching its real runner
+    /// binary against `binary_out`, bounded by `Config::fuzz_timeout`, with `engine`'s
+    /// `FuzzerBackend::run_env` applied. Unlike the libFuzzer-ABI engines, these are not invoked
+    /// by running `binary_out` directly: they drive it through their own runner.
+    fn run_external_fuzzer(
+        &self,
+        binary_out: &Path,
+        corpus_dir: &Path,
+        engine: FuzzEngine,
+    ) -> Result<()> {
+        let timeout = Duration::from_secs(get_config().fuzz_timeout);
+        let mut command = match engine {
+            FuzzEngine::AflPlusPlus => {
+                let mut command = Command::new("afl-fuzz");
+                command
+                    .arg("-i")
+                    .arg(corpus_dir)
+                    .arg("-o")
+                    .arg(corpus_dir)
+                    .arg("-V")
+                    .arg(timeout.as_secs().to_string())
+                    .arg("--")
+                    .arg(binary_out)
+                    .arg("@@");
+                command
+            }
+            FuzzEngine::Honggfuzz => {
+                let mut command = Command::new("honggfuzz");
+                command
+                    .arg("-i")
+                    .arg(corpus_dir)
+                    .arg("-o")
+                    .arg(corpus_dir)
+                    .arg("--run_time")
+                    .arg(timeout.as_secs().to_string())
+                    .arg("--")
+                    .arg(binary_out);
+                command
+            }
+            FuzzEngine::LibFuzzer | FuzzEngine::LibAfl => {
+                eyre::bail!("run_external_fuzzer called for libFuzzer-ABI engine {engine:?}")
+            }
+        };
+        for (key, value) in engine.run_env() {
+            command.env(key, value);
+        }
+        match proc::run_command_with_timeout(command, timeout)? {
+            proc::Bounded::TimedOut => {
+                eyre::bail!("{engine:?} did not finish within {}s", timeout.as_secs())
+            }
+            proc::Bounded::Done(output) if !output.status.success() => {
+                eyre::bail!(
+                    "{engine:?} exited with an error: {}",
+                    String::from_utf8_lossy(&output.stderr)
+                )
+            }
+            proc::Bounded::Done(_) => Ok(()),
+        }
+    }
+
+    /// Not yet bounded by `Config::coverage_timeout`: `collect_code_coverage` runs the
+    /// instrumented binary directly rather than through [`proc::run_command_with_timeout`].
     pub fn is_program_coverage_correct(&self, program_path: &Path) -> Result<Option<ProgramError>> {
         log::trace!("test program is coverage correct: {program_path:?}");
         let work_dir = get_file_dirname(program_path);
         let time_logger = TimeUsage::new(work_dir.clone());
 
-        // compile fuzzer with coverage instrumented library.
+        // compile fuzzer with coverage instrumented library. A non-default engine's
+        // `coverage_flags` differ from the default `COVERAGE_FLAGS` constant (e.g. AFL++/
+        // honggfuzz drop `-fsanitize=fuzzer`, which they don't understand), so only the default
+        // libFuzzer engine goes through the shared `Compile::COVERAGE` path.
+        let engine = get_config().engine;
         let fuzzer_binary = program_path.with_extension("cov.out");
-        self.compile(vec![program_path], &fuzzer_binary, super::Compile::COVERAGE)?;
+        if engine == FuzzEngine::LibFuzzer {
+            self.compile(vec![program_path], &fuzzer_binary, super::Compile::COVERAGE)?;
+        } else {
+            self.compile_with_flags(
+                program_path,
+                &fuzzer_binary,
+                &engine.coverage_flags(),
+                &engine.link_args(),
+            )?;
+        }
 
         // Run the fuzzer on the previous synthesized corpus and collect coverage.
-        let corpus_dir: PathBuf = [work_dir.clone(), "corpus".into()].iter().collect();
+        let corpus_dir: PathBuf = [work_dir.clone(), engine.corpus_subdir().into()]
+            .iter()
+            .collect();
         let coverage = self.collect_code_coverage(
             Some(program_path),
             &fuzzer_binary,
@@ -176,16 +526,40 @@ impl Executor {
         for (i, has_err) in res.iter().enumerate() {
             let path = &program_paths[i];
             let dir = get_file_dirname(path);
-            cleanup_sanitize_dir(&dir)?;
             if let Some(err) = has_err {
                 // skip delete the hang and fuzzer error programs, those may contain true bugs.
-                if let ProgramError::Hang(_) = err {
-                    continue;
-                }
-                if let ProgramError::Fuzzer(_) = err {
-                    continue;
+                // triage them into a deduplicated crash/hang bucket instead of just leaving the
+                // directory behind. This must run before `cleanup_sanitize_dir`, which would
+                // otherwise delete the corpus directory the crashing/hanging input lives in.
+                match err {
+                    ProgramError::Hang(report) => {
+                        triage::record_finding(
+                            &self.deopt,
+                            triage::TriageKind::Hang,
+                            path,
+                            &dir,
+                            report,
+                        )?;
+                        cleanup_sanitize_dir(&dir)?;
+                        continue;
+                    }
+                    ProgramError::Fuzzer(report) => {
+                        triage::record_finding(
+                            &self.deopt,
+                            triage::TriageKind::Crash,
+                            path,
+                            &dir,
+                            report,
+                        )?;
+                        cleanup_sanitize_dir(&dir)?;
+                        continue;
+                    }
+                    _ => {}
                 }
+                cleanup_sanitize_dir(&dir)?;
                 std::fs::remove_dir_all(dir)?;
+            } else {
+                cleanup_sanitize_dir(&dir)?;
             }
         }
         Ok(res)
@@ -277,7 +651,9 @@ impl Executor {
             GlobalFeature::init_by_corpus(self, &fuzzer_binary)?
         };
 
-        let corpus: PathBuf = [work_dir.clone(), "corpus".into()].iter().collect();
+        let corpus: PathBuf = [work_dir.clone(), get_config().engine.corpus_subdir().into()]
+            .iter()
+            .collect();
         let control_file: PathBuf = [work_dir, "merge_control_file".into()].iter().collect();
         self.minimize_by_control_file(&fuzzer_binary, &corpus, &control_file)?;
 
@@ -305,6 +681,12 @@ impl Executor {
         let buf = serde_json::to_vec(&global_featuers)?;
         std::fs::write(global_feature_file, buf)?;
         std::fs::remove_file(control_file)?;
+
+        // append this round's coverage/corpus-size snapshot so a campaign's growth can be
+        // plotted afterwards.
+        let round = crate::feedback::report::load_time_series(&self.deopt)?.len();
+        crate::feedback::report::record_time_series_point(&self.deopt, round)?;
+
         time_logger.log("update")?;
         Ok(())
     }
@@ -399,6 +781,26 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn test_parse_and_apply_fixit() {
+        let line = r#"fix-it:"prog.cc":{2:12-2:12}:";""#;
+        let fixit = parse_fixit_line(line).expect("should parse a fix-it line");
+        assert_eq!(fixit.start_line, 2);
+        assert_eq!(fixit.start_col, 12);
+        assert_eq!(fixit.end_line, 2);
+        assert_eq!(fixit.end_col, 12);
+        assert_eq!(fixit.replacement, ";");
+
+        let src = "int main() {\n  int x = 1\n}\n";
+        let repaired = apply_fixits(src, vec![fixit]);
+        assert_eq!(repaired, "int main() {\n  int x = 1;\n}\n");
+    }
+
+    #[test]
+    fn test_non_fixit_line_is_ignored() {
+        assert!(parse_fixit_line("error: use of undeclared identifier 'x'").is_none());
+    }
+
     #[test]
     fn test_coverage_sanitize() -> Result<()> {
         crate::config::Config::init_test("cJSON");
@@ -0,0 +1,227 @@
+//! Crash/hang triage: stack-trace deduplication and bucketed reproducers.
+//!
+//! `check_programs_are_correct` deliberately preserves `ProgramError::Hang` and
+//! `ProgramError::Fuzzer` programs because "those may contain true bugs", but without
+//! organization every run just leaves directories behind. This module adopts the
+//! `corpus/crashes/hangs` layout cargo-test-fuzz uses: it parses the AddressSanitizer (or
+//! hang) report for its top stack frames, normalizes them by stripping addresses/offsets and
+//! keeping symbol names, and hashes that tuple into a bucket id. The offending driver, the
+//! report, and (if found under the program's working directory) the minimized crashing input
+//! libFuzzer itself wrote out are stored under `crashes/<bucket>/` (or `hangs/<bucket>/`), and
+//! findings whose bucket already exists are dropped so duplicate crashes from the same root
+//! cause don't accumulate.
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+
+use eyre::Result;
+
+use crate::Deopt;
+
+/// How many top stack frames are kept when computing a crash's dedup signature. Deep frames
+/// tend to be libc/runtime noise that is the same across otherwise-distinct bugs.
+const TRIAGE_FRAME_DEPTH: usize = 5;
+
+/// Whether a triaged finding is a genuine crash or a timeout/hang.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TriageKind {
+    Crash,
+    Hang,
+}
+
+impl TriageKind {
+    fn dir_name(&self) -> &'static str {
+        match self {
+            TriageKind::Crash => "crashes",
+            TriageKind::Hang => "hangs",
+        }
+    }
+}
+
+/// The `crashes/` or `hangs/` root for `deopt`'s target.
+fn triage_root(deopt: &Deopt, kind: TriageKind) -> Result<PathBuf> {
+    let misc_path = deopt.get_library_misc_dir()?;
+    let root: PathBuf = [misc_path, kind.dir_name().into()].iter().collect();
+    crate::deopt::utils::create_dir_if_nonexist(&root)?;
+    Ok(root)
+}
+
+/// Extract and normalize the top [`TRIAGE_FRAME_DEPTH`] stack frames from a sanitizer report,
+/// i.e. lines beginning with `#0`, `#1`, ... Each frame is stripped of addresses and
+/// line/column offsets, keeping only the symbol name, so the same root cause buckets together
+/// across ASLR-shifted or slightly-differently-optimized runs.
+fn top_stack_frames(report: &str) -> Vec<String> {
+    report
+        .lines()
+        .filter(|line| {
+            let trimmed = line.trim_start();
+            trimmed.starts_with('#') && trimmed[1..].chars().next().is_some_and(|c| c.is_ascii_digit())
+        })
+        .take(TRIAGE_FRAME_DEPTH)
+        .map(normalize_frame)
+        .collect()
+}
+
+/// Strip a stack frame down to its symbol name, e.g.
+/// `    #3 0x5596a1 in cJSON_Parse /src/cJSON.c:123:45` -> `cJSON_Parse`.
+fn normalize_frame(line: &str) -> String {
+    let line = line.trim_start();
+    // drop the leading `#N ` marker
+    let line = line.splitn(2, ' ').nth(1).unwrap_or(line).trim_start();
+    // drop the `0xADDRESS ` if present
+    let line = if line.starts_with("0x") {
+        line.splitn(2, ' ').nth(1).unwrap_or(line).trim_start()
+    } else {
+        line
+    };
+    // drop the `in ` marker ASan/UBSan print before the symbol
+    let line = line.strip_prefix("in ").unwrap_or(line);
+    // keep only the symbol name, dropping the ` /path/to/file.c:line:col` suffix
+    line.split(' ').next().unwrap_or(line).to_string()
+}
+
+/// Hash a normalized frame tuple into a stable, filesystem-safe bucket id.
+fn bucket_id(frames: &[String]) -> String {
+    let mut hasher = DefaultHasher::new();
+    frames.hash(&mut hasher);
+    format!("{:016x}", hasher.finish())
+}
+
+/// The filename prefixes libFuzzer gives the reproducer it writes out for a crashing/hanging/
+/// leaking/OOM-ing run (<https://llvm.org/docs/LibFuzzer.html#crash-input>).
+const LIBFUZZER_ARTIFACT_PREFIXES: [&str; 4] = ["crash-", "leak-", "timeout-", "oom-"];
+
+/// Search `search_dir` (recursively) for a libFuzzer-style reproducer artifact, returning the
+/// first match. There is normally at most one, since `check_programs_are_correct` triages a
+/// program as soon as one of its stages reports a fuzzer error or a hang.
+fn find_crash_artifact(search_dir: &Path) -> Result<Option<PathBuf>> {
+    for entry in crate::deopt::utils::read_sort_dir(search_dir)? {
+        if entry.is_dir() {
+            if let Some(found) = find_crash_artifact(&entry)? {
+                return Ok(Some(found));
+            }
+            continue;
+        }
+        if let Some(name) = entry.file_name().and_then(|n| n.to_str()) {
+            if LIBFUZZER_ARTIFACT_PREFIXES
+                .iter()
+                .any(|prefix| name.starts_with(prefix))
+            {
+                return Ok(Some(entry));
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// Triage a crash/hang report: if its bucket is new, persist the offending driver, the report,
+/// and (if one can be found under `search_dir`, normally the program's working directory) the
+/// minimized crashing input libFuzzer wrote out, under `crashes/<bucket>/` or `hangs/<bucket>/`;
+/// returns `true`. If the bucket already exists, drop the duplicate and return `false`.
+pub fn record_finding(
+    deopt: &Deopt,
+    kind: TriageKind,
+    driver_path: &Path,
+    search_dir: &Path,
+    report: &str,
+) -> Result<bool> {
+    let frames = top_stack_frames(report);
+    let bucket = bucket_id(&frames);
+    let bucket_dir = triage_root(deopt, kind)?.join(&bucket);
+    if bucket_dir.exists() {
+        return Ok(false);
+    }
+    std::fs::create_dir_all(&bucket_dir)?;
+    if let Some(file_name) = driver_path.file_name() {
+        std::fs::copy(driver_path, bucket_dir.join(file_name))?;
+    }
+    if let Some(crashing_input) = find_crash_artifact(search_dir)? {
+        if let Some(file_name) = crashing_input.file_name() {
+            std::fs::copy(&crashing_input, bucket_dir.join(file_name))?;
+        }
+    }
+    std::fs::write(bucket_dir.join("report.txt"), report)?;
+    Ok(true)
+}
+
+/// List the distinct crash/hang buckets found so far, so users can see how many unique bugs a
+/// campaign actually found.
+pub fn list_buckets(deopt: &Deopt, kind: TriageKind) -> Result<Vec<String>> {
+    let root = triage_root(deopt, kind)?;
+    let mut buckets = Vec::new();
+    for entry in crate::deopt::utils::read_sort_dir(&root)? {
+        if entry.is_dir() {
+            if let Some(name) = entry.file_name().and_then(|n| n.to_str()) {
+                buckets.push(name.to_string());
+            }
+        }
+    }
+    Ok(buckets)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_frame_strips_address_and_location() {
+        let frame = "    #3 0x5596a1b2c3d4 in cJSON_Parse /src/cJSON.c:123:45";
+        assert_eq!(normalize_frame(frame), "cJSON_Parse");
+    }
+
+    #[test]
+    fn test_top_stack_frames_caps_at_depth() {
+        let report = (0..10)
+            .map(|i| format!("    #{i} 0x0 in frame_{i} /src/f.c:1:1"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert_eq!(top_stack_frames(&report).len(), TRIAGE_FRAME_DEPTH);
+    }
+
+    #[test]
+    fn test_bucket_id_is_stable_and_order_sensitive() {
+        let a = vec!["foo".to_string(), "bar".to_string()];
+        let b = vec!["foo".to_string(), "bar".to_string()];
+        let c = vec!["bar".to_string(), "foo".to_string()];
+        assert_eq!(bucket_id(&a), bucket_id(&b));
+        assert_ne!(bucket_id(&a), bucket_id(&c));
+    }
+
+    #[test]
+    fn test_find_crash_artifact_finds_nested_libfuzzer_reproducer() -> Result<()> {
+        let dir = std::env::temp_dir().join(format!(
+            "promptfuzz_triage_test_{}_{}",
+            std::process::id(),
+            "find_nested"
+        ));
+        std::fs::create_dir_all(dir.join("corpus"))?;
+        std::fs::write(dir.join("driver.cc"), "int main() {}")?;
+        std::fs::write(
+            dir.join("corpus").join("crash-deadbeef"),
+            b"\x00\x01\x02",
+        )?;
+
+        let found = find_crash_artifact(&dir)?;
+        assert_eq!(found, Some(dir.join("corpus").join("crash-deadbeef")));
+
+        std::fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_find_crash_artifact_none_when_absent() -> Result<()> {
+        let dir = std::env::temp_dir().join(format!(
+            "promptfuzz_triage_test_{}_{}",
+            std::process::id(),
+            "find_none"
+        ));
+        std::fs::create_dir_all(&dir)?;
+        std::fs::write(dir.join("driver.cc"), "int main() {}")?;
+
+        assert_eq!(find_crash_artifact(&dir)?, None);
+
+        std::fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+}
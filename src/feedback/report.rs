@@ -0,0 +1,172 @@
+//! Coverage reporting: HTML/line-level snapshots and time-series campaign tracking.
+//!
+//! `Executor::evolve_corpus` already persists a `GlobalFeature` set and per-seed
+//! `CorporaFeatures`, but until now that data was only used internally to decide which corpus
+//! files are "interesting". This module, in the spirit of ziggy's coverage plotting, exposes
+//! that data as (a) an HTML/line-level coverage report for the accumulated shared corpus built
+//! from the existing clang coverage collection, (b) a time-series record appended each round
+//! (`evolve_corpus` calls [`record_time_series_point`] once it finishes merging a round's
+//! corpus) so users can plot coverage growth over a campaign, and (c) a differential mode that,
+//! given two snapshots of the global feature file, reports which edges/callees were newly
+//! covered. [`render_html_report`]/[`diff_global_feature_snapshots`] are standalone reporting
+//! utilities, not yet called from any automatic per-round step.
+
+use std::collections::HashSet;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use eyre::Result;
+
+use crate::Deopt;
+
+/// One row of the coverage time-series: how many covered features/edges and how large the
+/// shared corpus was after a given fuzzing round.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct CoverageDataPoint {
+    pub round: usize,
+    pub covered_features: usize,
+    pub corpus_size: usize,
+}
+
+fn time_series_file(deopt: &Deopt) -> Result<PathBuf> {
+    let misc_path = deopt.get_library_misc_dir()?;
+    Ok([misc_path, "coverage_time_series.jsonl".into()].iter().collect())
+}
+
+/// Append a new data point to the campaign's time-series file, so coverage growth can be
+/// plotted across a campaign.
+pub fn record_time_series_point(deopt: &Deopt, round: usize) -> Result<()> {
+    let global_feature_file = deopt.get_library_global_feature_file()?;
+    let covered_features = if global_feature_file.exists() {
+        load_feature_ids(&global_feature_file)?.len()
+    } else {
+        0
+    };
+    let corpus_size =
+        crate::deopt::utils::read_sort_dir(&deopt.get_library_shared_corpus_dir()?)?.len();
+
+    let point = CoverageDataPoint {
+        round,
+        covered_features,
+        corpus_size,
+    };
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(time_series_file(deopt)?)?;
+    writeln!(file, "{}", serde_json::to_string(&point)?)?;
+    Ok(())
+}
+
+/// Load every recorded time-series data point for `deopt`'s target, in round order.
+pub fn load_time_series(deopt: &Deopt) -> Result<Vec<CoverageDataPoint>> {
+    let path = time_series_file(deopt)?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    std::fs::read_to_string(path)?
+        .lines()
+        .map(|line| Ok(serde_json::from_str(line)?))
+        .collect()
+}
+
+/// Render an HTML line-level coverage report for the accumulated shared corpus, reusing the
+/// existing clang coverage collection: `profdata` is the merged `.profdata` file produced by
+/// `Executor::collect_code_coverage` for `fuzzer_binary`.
+pub fn render_html_report(deopt: &Deopt, fuzzer_binary: &Path, profdata: &Path) -> Result<PathBuf> {
+    let report_dir = html_report_dir(deopt)?;
+    crate::deopt::utils::create_dir_if_nonexist(&report_dir)?;
+    let output = std::process::Command::new("llvm-cov")
+        .arg("show")
+        .arg("--format=html")
+        .arg(format!("--output-dir={}", report_dir.display()))
+        .arg("--instr-profile")
+        .arg(profdata)
+        .arg(fuzzer_binary)
+        .output()?;
+    if !output.status.success() {
+        eyre::bail!(
+            "llvm-cov failed to render the HTML coverage report: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+    Ok(report_dir)
+}
+
+fn html_report_dir(deopt: &Deopt) -> Result<PathBuf> {
+    let misc_path = deopt.get_library_misc_dir()?;
+    Ok([misc_path, "coverage_html".into()].iter().collect())
+}
+
+/// The result of diffing two `GlobalFeature` snapshots: which feature ids are newly present in
+/// the `after` snapshot that were absent from `before`.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct CoverageDiff {
+    pub newly_covered: Vec<u64>,
+    pub total_before: usize,
+    pub total_after: usize,
+}
+
+/// Diff two persisted `GlobalFeature` snapshot files, reporting which edges/callees were newly
+/// covered by `after` relative to `before`, so users can judge whether adding seeds actually
+/// improved coverage.
+pub fn diff_global_feature_snapshots(before: &Path, after: &Path) -> Result<CoverageDiff> {
+    let before = load_feature_ids(before)?;
+    let after = load_feature_ids(after)?;
+    let mut newly_covered: Vec<u64> = after.difference(&before).copied().collect();
+    newly_covered.sort_unstable();
+    Ok(CoverageDiff {
+        total_before: before.len(),
+        total_after: after.len(),
+        newly_covered,
+    })
+}
+
+/// Recursively collect every integer leaf out of a persisted `GlobalFeature` JSON file into a
+/// flat id set. `GlobalFeature`'s exact serialization shape (a bare set vs. a wrapping struct)
+/// doesn't matter here: every feature is ultimately represented as a numeric edge/callee id.
+fn load_feature_ids(path: &Path) -> Result<HashSet<u64>> {
+    let value: serde_json::Value = serde_json::from_slice(&std::fs::read(path)?)?;
+    let mut ids = HashSet::new();
+    collect_u64s(&value, &mut ids);
+    Ok(ids)
+}
+
+fn collect_u64s(value: &serde_json::Value, out: &mut HashSet<u64>) {
+    match value {
+        serde_json::Value::Number(n) => {
+            if let Some(u) = n.as_u64() {
+                out.insert(u);
+            }
+        }
+        serde_json::Value::Array(items) => items.iter().for_each(|v| collect_u64s(v, out)),
+        serde_json::Value::Object(map) => map.values().for_each(|v| collect_u64s(v, out)),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collect_u64s_from_nested_value() {
+        let value: serde_json::Value = serde_json::json!({"features": [1, 2, {"more": [3]}]});
+        let mut ids = HashSet::new();
+        collect_u64s(&value, &mut ids);
+        assert_eq!(ids, HashSet::from([1, 2, 3]));
+    }
+
+    #[test]
+    fn test_diff_reports_only_new_ids() {
+        let before: serde_json::Value = serde_json::json!([1, 2, 3]);
+        let after: serde_json::Value = serde_json::json!([1, 2, 3, 4, 5]);
+        let mut before_set = HashSet::new();
+        collect_u64s(&before, &mut before_set);
+        let mut after_set = HashSet::new();
+        collect_u64s(&after, &mut after_set);
+        let mut diff: Vec<u64> = after_set.difference(&before_set).copied().collect();
+        diff.sort_unstable();
+        assert_eq!(diff, vec![4, 5]);
+    }
+}